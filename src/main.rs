@@ -1,8 +1,9 @@
 use macroquad::{
     input::is_key_down,
     prelude::{
-        draw_arc, draw_circle, draw_circle_lines, draw_rectangle, draw_text, is_key_pressed,
-        next_frame, screen_height, screen_width, Color, KeyCode, Vec2, BLACK, GREEN, RED, WHITE,
+        draw_arc, draw_circle, draw_circle_lines, draw_rectangle, draw_text, draw_triangle,
+        is_key_pressed, next_frame, screen_height, screen_width, Color, KeyCode, Vec2, BLACK,
+        GREEN, RED, WHITE, YELLOW,
     },
 };
 use rand::{thread_rng, Rng};
@@ -10,11 +11,25 @@ use std::collections::HashMap;
 
 const DST_SIZE: f32 = 5.0;
 const SHIP_SIZE: f32 = 10.0;
+const SHIP_ACC: f32 = 0.05;
+const SHIP_DRAG: f32 = 0.001;
+const SHIP_ROT_SPEED: f32 = 0.05;
+const SHIP_HULL_TOLERANCE: f32 = 2.0;
 const NUM_ASTEROIDS: usize = 10;
 const ARC_GROWTH_RATE: f32 = 1.0;
 const SCAN_SPEED: usize = 7;
 const ANGLE_PRECISION: usize = 100;
-const CLOSENESS_TOLERANCE: f32 = 0.3;
+const ASTEROID_MAX_SPEED: f32 = 0.5;
+const ASTEROID_MAX_OMEGA: f32 = 0.5;
+const ASTEROID_TIERS: [f32; 3] = [35.0, 20.0, 10.0]; // large -> medium -> small, then gone
+const BULLET_SPEED: f32 = 4.0;
+const BULLET_TTL: usize = 90;
+const BULLET_RADIUS: f32 = 2.0;
+const SPEEDUP_STEPS: usize = 1000;
+const MAX_LIFESPAN: usize = 5000;
+const AI_RADAR_ASTEROIDS: usize = 5;
+const AI_MUT_RATE: f32 = 0.04;
+const AI_DESTINATION_BONUS: f32 = 1000.0;
 
 // struct Radar {}
 // impl Iterator for Radar {
@@ -40,21 +55,25 @@ impl Line {
     }
 
     fn near(&self, point: Vec2, tolerance: f32) -> bool {
-        // Calculate the cross product to ensure the point is on the infinite line
-        let cross_product = (point.y - self.a.y) * (self.b.x - self.a.x)
-            - (point.x - self.a.x) * (self.b.y - self.a.y);
-        if cross_product.abs() > tolerance {
+        let edge = self.b - self.a;
+        let to_point = point - self.a;
+        let squared_length = edge.length_squared();
+
+        // Perpendicular distance from `point` to the infinite line, normalized by
+        // edge length (the raw cross product scales with edge length, so without
+        // this a tolerance in pixels would shrink to nothing on long edges).
+        let cross_product = edge.x * to_point.y - edge.y * to_point.x;
+        let perpendicular_distance = cross_product.abs() / squared_length.sqrt();
+        if perpendicular_distance > tolerance {
             return false; // Not collinear
         }
 
-        // Check if the point lies within the bounds of the segment
-        let dot_product = (point.x - self.a.x) * (self.b.x - self.a.x)
-            + (point.y - self.a.y) * (self.b.y - self.a.y);
-        if dot_product < tolerance {
+        // Check if the point's projection lies within the bounds of the segment
+        let dot_product = to_point.x * edge.x + to_point.y * edge.y;
+        if dot_product < 0.0 {
             return false; // Point is before `a`
         }
 
-        let squared_length = (self.b.x - self.a.x).powi(2) + (self.b.y - self.a.y).powi(2);
         if dot_product > squared_length {
             return false; // Point is after `b`
         }
@@ -63,12 +82,250 @@ impl Line {
     }
 }
 
+// Exact ray/segment intersection: for ray `origin + t*(cos angle, sin angle)` and
+// segment `a -> b`, solves the 2x2 system for `t` along the ray and `u` along the
+// segment, keeping the smallest valid `t`. Returns `None` when no edge is hit.
+fn cast_ray(origin: Vec2, angle: f32, edges: &[Line]) -> Option<f32> {
+    let dir = Vec2::new(angle.cos(), angle.sin());
+    let mut closest: Option<f32> = None;
+    for edge in edges {
+        let seg = edge.b - edge.a;
+        let diff = edge.a - origin;
+        let denom = seg.x * dir.y - seg.y * dir.x;
+        if denom.abs() < f32::EPSILON {
+            continue; // ray parallel to this edge
+        }
+        let t = (seg.x * diff.y - seg.y * diff.x) / denom;
+        let u = (dir.x * diff.y - dir.y * diff.x) / denom;
+        if t >= 0.0 && (0.0..=1.0).contains(&u) && closest.map_or(true, |best| t < best) {
+            closest = Some(t);
+        }
+    }
+    closest
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Activation {
+    ReLU,
+    Sigmoid,
+    Tanh,
+}
+
+impl Activation {
+    fn apply(&self, x: f32) -> f32 {
+        match self {
+            Activation::ReLU => x.max(0.0),
+            Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Activation::Tanh => x.tanh(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f32>,
+}
+
+impl Matrix {
+    fn random(rows: usize, cols: usize) -> Self {
+        let mut rng = thread_rng();
+        Self {
+            rows,
+            cols,
+            data: (0..rows * cols).map(|_| rng.gen_range(-1.0..=1.0)).collect(),
+        }
+    }
+
+    fn get(&self, row: usize, col: usize) -> f32 {
+        self.data[row * self.cols + col]
+    }
+
+    // Multiplies this matrix by a column vector, i.e. one NN layer's forward pass.
+    fn mul_vec(&self, input: &[f32]) -> Vec<f32> {
+        (0..self.rows)
+            .map(|row| (0..self.cols).map(|col| self.get(row, col) * input[col]).sum())
+            .collect()
+    }
+}
+
+// Feed-forward network: `config` is the layer sizes (inputs, hiddens, outputs) and
+// `weights[i]` maps layer `i` to layer `i + 1`, with an extra bias column per row.
+#[derive(Debug, Clone)]
+struct NN {
+    config: Vec<usize>,
+    weights: Vec<Matrix>,
+    activation: Activation,
+}
+
+impl NN {
+    fn new(config: Vec<usize>, activation: Activation) -> Self {
+        let weights = config
+            .windows(2)
+            .map(|layer_sizes| Matrix::random(layer_sizes[1], layer_sizes[0] + 1))
+            .collect();
+        Self {
+            config,
+            weights,
+            activation,
+        }
+    }
+
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let mut activations = input.to_vec();
+        for layer in &self.weights {
+            activations.push(1.0); // bias term
+            activations = layer
+                .mul_vec(&activations)
+                .into_iter()
+                .map(|x| self.activation.apply(x))
+                .collect();
+        }
+        activations
+    }
+}
+
+// Picks a parent weighted by fitness (roulette wheel), falling back to a uniform
+// pick if every fitness is non-positive.
+fn select_parent<'a>(players: &'a [NN], fitness: &[f32], total_fitness: f32, rng: &mut impl Rng) -> &'a NN {
+    if total_fitness <= 0.0 {
+        return &players[rng.gen_range(0..players.len())];
+    }
+    let mut target = rng.gen_range(0.0..total_fitness);
+    for (player, &f) in players.iter().zip(fitness) {
+        target -= f.max(0.0);
+        if target <= 0.0 {
+            return player;
+        }
+    }
+    players.last().unwrap()
+}
+
+fn crossover(a: &NN, b: &NN, mut_rate: f32, rng: &mut impl Rng) -> NN {
+    let weights = a
+        .weights
+        .iter()
+        .zip(&b.weights)
+        .map(|(wa, wb)| {
+            let data = wa
+                .data
+                .iter()
+                .zip(&wb.data)
+                .map(|(&x, &y)| {
+                    let picked = if rng.gen_bool(0.5) { x } else { y };
+                    if rng.gen_bool(mut_rate as f64) {
+                        rng.gen_range(-1.0..=1.0)
+                    } else {
+                        picked
+                    }
+                })
+                .collect();
+            Matrix {
+                rows: wa.rows,
+                cols: wa.cols,
+                data,
+            }
+        })
+        .collect();
+    NN {
+        config: a.config.clone(),
+        weights,
+        activation: a.activation,
+    }
+}
+
+// A generation of autopilots. Score each player's `play_level` run with `fitness`,
+// then call `evolve` to breed the next generation by crossover and mutation.
+struct Population {
+    players: Vec<NN>,
+    mut_rate: f32,
+}
+
+impl Population {
+    fn new(size: usize, config: Vec<usize>, activation: Activation, mut_rate: f32) -> Self {
+        let players = (0..size).map(|_| NN::new(config.clone(), activation)).collect();
+        Self { players, mut_rate }
+    }
+
+    fn evolve(&mut self, fitness: &[f32]) {
+        let mut rng = thread_rng();
+        let total_fitness: f32 = fitness.iter().map(|f| f.max(0.0)).sum();
+        self.players = (0..self.players.len())
+            .map(|_| {
+                let parent_a = select_parent(&self.players, fitness, total_fitness, &mut rng);
+                let parent_b = select_parent(&self.players, fitness, total_fitness, &mut rng);
+                crossover(parent_a, parent_b, self.mut_rate, &mut rng)
+            })
+            .collect();
+    }
+}
+
+// fitness = how long the ship survived, big bonus for reaching the destination,
+// penalized by however far from it the ship ended up.
+fn fitness(lifespan: usize, reached_destination: bool, distance_to_destination: f32) -> f32 {
+    let bonus = if reached_destination { AI_DESTINATION_BONUS } else { 0.0 };
+    lifespan as f32 + bonus - distance_to_destination
+}
+
+// Newtonian flight model: `dir`/`rot` track heading, thrust accumulates into `vel`,
+// and drag bleeds it off each frame so a mistimed burn leaves the ship drifting.
+#[derive(Debug)]
+struct Ship {
+    pos: Vec2,
+    dir: Vec2,
+    rot: f32,
+    vel: Vec2,
+}
+
+impl Ship {
+    fn new(pos: Vec2) -> Self {
+        let rot = -std::f32::consts::FRAC_PI_2; // pointing up
+        Self {
+            pos,
+            dir: Vec2::new(rot.cos(), rot.sin()),
+            rot,
+            vel: Vec2::new(0.0, 0.0),
+        }
+    }
+
+    fn update(&mut self, rotate: f32, thrust: f32) {
+        self.rot += rotate;
+        self.dir = Vec2::new(self.rot.cos(), self.rot.sin());
+        self.vel += self.dir * thrust * SHIP_ACC;
+        self.vel *= 1.0 - SHIP_DRAG;
+        self.pos += self.vel;
+    }
+
+    // Triangle hull pointing along `dir`, tip first.
+    fn hull(&self) -> [Vec2; 3] {
+        let perp = Vec2::new(-self.dir.y, self.dir.x);
+        [
+            self.pos + self.dir * SHIP_SIZE,
+            self.pos - self.dir * SHIP_SIZE * 0.6 + perp * SHIP_SIZE * 0.6,
+            self.pos - self.dir * SHIP_SIZE * 0.6 - perp * SHIP_SIZE * 0.6,
+        ]
+    }
+
+    fn edges(&self) -> Vec<Line> {
+        let [tip, left, right] = self.hull();
+        vec![
+            Line::new(tip, left),
+            Line::new(left, right),
+            Line::new(right, tip),
+        ]
+    }
+}
+
 #[derive(Debug)]
 struct Asteroid {
     pos: Vec2,
     sides: u8,
     radius: f32,
     rotation: f32,
+    vel: Vec2,
+    omega: f32,
+    tier: usize,
 }
 
 impl Asteroid {
@@ -81,8 +338,14 @@ impl Asteroid {
                 rng.gen_range(0.0..=screen_height()),
             ),
             sides: rng.gen_range(3..8),
-            radius: rng.gen_range(5.0..40.0),
+            radius: ASTEROID_TIERS[0],
             rotation: rng.gen_range(0.0..360.0),
+            vel: Vec2::new(
+                rng.gen_range(-ASTEROID_MAX_SPEED..=ASTEROID_MAX_SPEED),
+                rng.gen_range(-ASTEROID_MAX_SPEED..=ASTEROID_MAX_SPEED),
+            ),
+            omega: rng.gen_range(-ASTEROID_MAX_OMEGA..=ASTEROID_MAX_OMEGA),
+            tier: 0,
         };
         for edge in asteroid.edges() {
             if edge.near(ship, SHIP_SIZE) {
@@ -92,6 +355,50 @@ impl Asteroid {
         asteroid
     }
 
+    // Steps this asteroid down one size tier, returning the two smaller asteroids
+    // it splits into, or an empty vec once it's already at the smallest tier.
+    fn split(&self) -> Vec<Asteroid> {
+        let Some(&next_radius) = ASTEROID_TIERS.get(self.tier + 1) else {
+            return Vec::new();
+        };
+        let mut rng = thread_rng();
+        let base_angle = self.vel.y.atan2(self.vel.x);
+        let speed = self.vel.length().max(ASTEROID_MAX_SPEED) * 1.5;
+        [1.0, -1.0]
+            .into_iter()
+            .map(|side| {
+                let angle = base_angle + side * std::f32::consts::FRAC_PI_4;
+                Asteroid {
+                    pos: self.pos,
+                    sides: self.sides,
+                    radius: next_radius,
+                    rotation: self.rotation,
+                    vel: Vec2::new(angle.cos(), angle.sin()) * speed,
+                    omega: rng.gen_range(-ASTEROID_MAX_OMEGA..=ASTEROID_MAX_OMEGA),
+                    tier: self.tier + 1,
+                }
+            })
+            .collect()
+    }
+
+    // Advances position and spin by one frame, wrapping the position around
+    // whichever screen edge the asteroid's hull has crossed.
+    fn update(&mut self) {
+        self.pos += self.vel;
+        self.rotation += self.omega;
+
+        if self.pos.x < -self.radius {
+            self.pos.x = screen_width() + self.radius;
+        } else if self.pos.x > screen_width() + self.radius {
+            self.pos.x = -self.radius;
+        }
+        if self.pos.y < -self.radius {
+            self.pos.y = screen_height() + self.radius;
+        } else if self.pos.y > screen_height() + self.radius {
+            self.pos.y = -self.radius;
+        }
+    }
+
     fn vertices(&self) -> Vec<Vec2> {
         let mut vertices = Vec::new();
         for i in 0..self.sides {
@@ -117,6 +424,56 @@ impl Asteroid {
     }
 }
 
+#[derive(Debug)]
+struct Bullet {
+    pos: Vec2,
+    heading: Vec2,
+    ttl: usize,
+}
+
+impl Bullet {
+    fn new(pos: Vec2, heading: Vec2) -> Self {
+        Self {
+            pos,
+            heading,
+            ttl: BULLET_TTL,
+        }
+    }
+
+    fn update(&mut self) {
+        self.pos += self.heading * BULLET_SPEED;
+        self.ttl = self.ttl.saturating_sub(1);
+    }
+
+    fn alive(&self) -> bool {
+        self.ttl > 0
+    }
+}
+
+// Builds the autopilot's input vector: for the nearest `n` asteroids, normalized
+// distance, sin/cos of the angle from `ship` to it, and its normalized radius,
+// followed by the normalized vector from `ship` to `destination`.
+fn ai_inputs(ship: Vec2, destination: Vec2, asteroids: &[Asteroid], n: usize) -> Vec<f32> {
+    let mut by_distance: Vec<&Asteroid> = asteroids.iter().collect();
+    by_distance.sort_by(|a, b| ship.distance(a.pos).partial_cmp(&ship.distance(b.pos)).unwrap());
+
+    let mut inputs = Vec::with_capacity(n * 4 + 2);
+    for asteroid in by_distance.into_iter().take(n) {
+        let to_asteroid = asteroid.pos - ship;
+        let angle = to_asteroid.y.atan2(to_asteroid.x);
+        inputs.push(to_asteroid.length() / screen_width());
+        inputs.push(angle.sin());
+        inputs.push(angle.cos());
+        inputs.push(asteroid.radius / screen_width());
+    }
+    inputs.resize(n * 4, 0.0);
+
+    let to_destination = destination - ship;
+    inputs.push(to_destination.x / screen_width());
+    inputs.push(to_destination.y / screen_height());
+    inputs
+}
+
 fn polar2euclidean(center: Vec2, radius: f32, angle: f32) -> Vec2 {
     Vec2::new(
         center.x + radius * angle.cos(),
@@ -124,24 +481,6 @@ fn polar2euclidean(center: Vec2, radius: f32, angle: f32) -> Vec2 {
     )
 }
 
-fn pixels_in_circle(
-    center: Vec2,
-    radius: f32,
-    excluded_angles: &HashMap<usize, usize>,
-) -> Vec<(Vec2, usize)> {
-    let mut pixels = Vec::new();
-    for angle in 0..=360 * ANGLE_PRECISION {
-        if excluded_angles.contains_key(&angle) {
-            continue;
-        }
-        pixels.push((
-            polar2euclidean(center, radius, angle as f32 / ANGLE_PRECISION as f32),
-            angle,
-        ));
-    }
-    pixels
-}
-
 fn draw_circle_except_angles(
     center: Vec2,
     radius: f32,
@@ -181,19 +520,30 @@ async fn circle_render(edges: &Vec<Line>, center: Vec2, destination: Vec2, scans
     let mut excluded_angles: HashMap<usize, usize> = HashMap::new(); // (angle, radius)
     let mut drawn_pixels: Vec<Vec2> = Vec::new();
 
+    // Cast one ray per angle step up front; the sweep below just waits for the
+    // growing `scan_radius` to pass each ray's exact hit distance.
+    let hit_distances: Vec<Option<f32>> = (0..=360 * ANGLE_PRECISION)
+        .map(|angle| cast_ray(center, (angle as f32 / ANGLE_PRECISION as f32).to_radians(), edges))
+        .collect();
+
     for scan_radius in (SHIP_SIZE as usize..screen_width() as usize).step_by(SCAN_SPEED) {
         if interrupted_by_movement() {
             break;
         }
         draw_circle_except_angles(center, scan_radius as f32, 0.5, GREEN, &excluded_angles);
         // TODO: glitter background
-        for (pixel, angle) in pixels_in_circle(center, scan_radius as f32, &excluded_angles) {
-            for edge in edges {
-                if edge.near(pixel, CLOSENESS_TOLERANCE) {
-                    // draw pixel
-                    drawn_pixels.push(pixel);
+        for angle in 0..=360 * ANGLE_PRECISION {
+            if excluded_angles.contains_key(&angle) {
+                continue;
+            }
+            if let Some(distance) = hit_distances[angle] {
+                if scan_radius as f32 >= distance {
+                    drawn_pixels.push(polar2euclidean(
+                        center,
+                        distance,
+                        (angle as f32 / ANGLE_PRECISION as f32).to_radians(),
+                    ));
                     excluded_angles.insert(angle, scan_radius);
-                    break;
                 }
             }
         }
@@ -207,72 +557,206 @@ async fn circle_render(edges: &Vec<Line>, center: Vec2, destination: Vec2, scans
     }
 }
 
-async fn play_level(level: &usize) -> bool {
-    let mut scans = 12 - (level / 3) as usize;
-    let mut ship = Vec2::new(screen_width() / 2.0, screen_height() / 2.0);
-    let mut rng = thread_rng();
-    let destination = Vec2::new(
-        rng.gen_range(0.0..=screen_width()),
-        rng.gen_range(0.0..=screen_height()),
-    );
-    let mut asteroids: Vec<Asteroid> = Vec::new();
-    for _i in 0..NUM_ASTEROIDS + level {
-        let asteroid = Asteroid::random_asteroid(ship);
-        asteroids.push(asteroid);
-    }
-    let mut edges = Vec::new();
-    for asteroid in &asteroids {
-        edges.extend(asteroid.edges());
+enum Outcome {
+    Crashed,
+    Reached,
+}
+
+// Owns everything the simulation needs to advance a level, independent of drawing.
+// `step` is the pure per-frame update; `play_level` wraps it with rendering.
+struct LevelState<'a> {
+    scans: usize,
+    ship: Ship,
+    destination: Vec2,
+    asteroids: Vec<Asteroid>,
+    bullets: Vec<Bullet>,
+    lifespan: usize,
+    ai: Option<&'a NN>,
+}
+
+impl<'a> LevelState<'a> {
+    fn new(level: usize, ai: Option<&'a NN>) -> Self {
+        let ship = Ship::new(Vec2::new(screen_width() / 2.0, screen_height() / 2.0));
+        let mut rng = thread_rng();
+        let destination = Vec2::new(
+            rng.gen_range(0.0..=screen_width()),
+            rng.gen_range(0.0..=screen_height()),
+        );
+        let asteroids = (0..NUM_ASTEROIDS + level)
+            .map(|_| Asteroid::random_asteroid(ship.pos))
+            .collect();
+        Self {
+            scans: 12 - (level / 3),
+            ship,
+            destination,
+            asteroids,
+            bullets: Vec::new(),
+            lifespan: 0,
+            ai,
+        }
     }
 
-    loop {
-        draw_text(&format!("Scans: {scans}"), 20.0, 20.0, 20.0, WHITE);
-        draw_circle(ship.x, ship.y, SHIP_SIZE, WHITE);
-        draw_circle(destination.x, destination.y, DST_SIZE, RED);
-        // if J pressed
-        if is_key_pressed(KeyCode::Space) {
-            if scans == 0 {
-                // TODO: beep wrong sound
-            } else {
-                scans -= 1;
-                circle_render(&edges, ship, destination, scans).await;
-            }
+    fn edges(&self) -> Vec<Line> {
+        let mut edges = Vec::new();
+        for asteroid in &self.asteroids {
+            edges.extend(asteroid.edges());
         }
-        if is_key_down(KeyCode::W) && ship.y >= 0.0 + SHIP_SIZE {
-            // up
-            ship.y -= 1.0;
+        edges
+    }
+
+    // Advances asteroids, bullets, ship and collision/destination checks by one
+    // frame. No drawing, no `next_frame().await`, and no reads of macroquad's
+    // input globals, so this can run headless at whatever speed the caller wants;
+    // the caller samples input once per rendered frame and passes it in.
+    fn step(&mut self, fire: bool, keys: (bool, bool, bool, bool)) -> Option<Outcome> {
+        self.lifespan += 1;
+        // a net that thrusts off-screen forever would otherwise never crash or
+        // reach the destination, hanging a headless run indefinitely
+        if self.lifespan >= MAX_LIFESPAN {
+            return Some(Outcome::Crashed);
         }
-        if is_key_down(KeyCode::S) && ship.y <= screen_height() - SHIP_SIZE {
-            // down
-            ship.y += 1.0;
+        for asteroid in &mut self.asteroids {
+            asteroid.update();
         }
-        if is_key_down(KeyCode::A) && ship.x >= 0.0 + SHIP_SIZE {
-            // left
-            ship.x -= 1.0;
+
+        // fire a bullet along the ship's heading, if the caller sampled a press
+        if fire {
+            self.bullets.push(Bullet::new(self.ship.pos, self.ship.dir));
         }
-        if is_key_down(KeyCode::D) && ship.x <= screen_width() - SHIP_SIZE {
-            // right
-            ship.x += 1.0;
+        for bullet in &mut self.bullets {
+            bullet.update();
         }
-        // if ship touched an asteroid, die
-        for asteroid in &asteroids {
-            for edge in asteroid.edges() {
-                if edge.near(ship, SHIP_SIZE) {
+        self.bullets.retain(Bullet::alive);
+
+        let mut hit_asteroids = Vec::new();
+        let mut spawned_asteroids = Vec::new();
+        let asteroids = &self.asteroids;
+        self.bullets.retain(|bullet| {
+            for (i, asteroid) in asteroids.iter().enumerate() {
+                if !hit_asteroids.contains(&i) && bullet.pos.distance(asteroid.pos) <= asteroid.radius {
+                    hit_asteroids.push(i);
+                    spawned_asteroids.extend(asteroid.split());
                     return false;
                 }
             }
+            true
+        });
+        hit_asteroids.sort_unstable();
+        for &i in hit_asteroids.iter().rev() {
+            self.asteroids.remove(i);
+        }
+        self.asteroids.extend(spawned_asteroids);
+
+        // asteroids drift and get shot every frame, so collision needs fresh edges
+        let edges = self.edges();
+
+        let (w, s, a, d) = match self.ai {
+            Some(nn) => {
+                let inputs = ai_inputs(self.ship.pos, self.destination, &self.asteroids, AI_RADAR_ASTEROIDS);
+                let outputs = nn.forward(&inputs);
+                (outputs[0] > 0.5, outputs[1] > 0.5, outputs[2] > 0.5, outputs[3] > 0.5)
+            }
+            None => keys,
+        };
+        let rotate = if a {
+            -SHIP_ROT_SPEED
+        } else if d {
+            SHIP_ROT_SPEED
+        } else {
+            0.0
+        };
+        let thrust = if w {
+            1.0
+        } else if s {
+            -1.0
+        } else {
+            0.0
+        };
+        self.ship.update(rotate, thrust);
+
+        // if ship touched an asteroid, die. Checked both ways (asteroid edges
+        // against hull vertices, hull edges against asteroid vertices) since a
+        // vertex-only test on either side alone can miss a real overlap.
+        let hull = self.ship.hull();
+        let hull_edges = self.ship.edges();
+        let asteroid_hit = edges.iter().any(|edge| hull.iter().any(|&vertex| edge.near(vertex, SHIP_HULL_TOLERANCE)))
+            || self.asteroids.iter().any(|asteroid| {
+                let vertices = asteroid.vertices();
+                hull_edges.iter().any(|edge| vertices.iter().any(|&vertex| edge.near(vertex, SHIP_HULL_TOLERANCE)))
+            });
+        if asteroid_hit {
+            return Some(Outcome::Crashed);
+        }
+        if self.ship.pos.distance(self.destination) <= SHIP_SIZE + DST_SIZE {
+            return Some(Outcome::Reached);
+        }
+        None
+    }
+}
+
+// Plays one level, either by keyboard or by an `ai` autopilot. `speedup` starts
+// the level running `SPEEDUP_STEPS` simulated frames per rendered one (toggled by
+// F during play), which is how whole populations get evaluated in seconds.
+// Returns whether the destination was reached along with the fitness of the run,
+// so this can double as the evaluation step for `Population::evolve`.
+async fn play_level(level: &usize, ai: Option<&NN>, mut speedup: bool) -> (bool, f32) {
+    let mut state = LevelState::new(*level, ai);
+
+    loop {
+        if is_key_pressed(KeyCode::F) {
+            speedup = !speedup;
+        }
+        // Sample input once per rendered frame, not once per simulated step, or a
+        // single `J` press / held key would get re-applied up to `SPEEDUP_STEPS`
+        // times. A momentary press only fires on the first step of the batch;
+        // held keys keep applying thrust/rotation across every step in it.
+        let fire = is_key_pressed(KeyCode::J);
+        let keys = (
+            is_key_down(KeyCode::W),
+            is_key_down(KeyCode::S),
+            is_key_down(KeyCode::A),
+            is_key_down(KeyCode::D),
+        );
+        let steps_this_frame = if speedup { SPEEDUP_STEPS } else { 1 };
+        let mut outcome = None;
+        for i in 0..steps_this_frame {
+            outcome = state.step(fire && i == 0, keys);
+            if outcome.is_some() {
+                break;
+            }
+        }
+        if let Some(outcome) = outcome {
+            let distance = state.ship.pos.distance(state.destination);
+            return match outcome {
+                Outcome::Reached => (true, fitness(state.lifespan, true, 0.0)),
+                Outcome::Crashed => (false, fitness(state.lifespan, false, distance)),
+            };
+        }
+
+        draw_text(&format!("Scans: {}", state.scans), 20.0, 20.0, 20.0, WHITE);
+        let hull = state.ship.hull();
+        draw_triangle(hull[0], hull[1], hull[2], WHITE);
+        draw_circle(state.destination.x, state.destination.y, DST_SIZE, RED);
+        for bullet in &state.bullets {
+            draw_circle(bullet.pos.x, bullet.pos.y, BULLET_RADIUS, YELLOW);
         }
-        if ship.distance(destination) <= SHIP_SIZE + DST_SIZE {
-            return true;
+        // if Space pressed
+        if is_key_pressed(KeyCode::Space) {
+            if state.scans == 0 {
+                // TODO: beep wrong sound
+            } else {
+                state.scans -= 1;
+                circle_render(&state.edges(), state.ship.pos, state.destination, state.scans).await;
+            }
         }
         next_frame().await;
     }
 }
 
-async fn play_game() -> (bool, bool) {
+async fn play_game(ai: Option<&NN>) -> (bool, bool) {
     let mut win = true;
     for level in 1..50 {
-        if !play_level(&level).await {
+        if !play_level(&level, ai, false).await.0 {
             win = false;
             break;
         }
@@ -313,10 +797,86 @@ async fn play_game() -> (bool, bool) {
     (win, play_again)
 }
 
+// Trains a population on level 1 for `generations` rounds and returns the fittest
+// survivor, so `main` can hand piloting over to it instead of the keyboard. Each
+// player's run starts in speedup mode so a whole generation evaluates in seconds.
+async fn train_autopilot(generations: usize, population_size: usize, activation: Activation) -> NN {
+    let config = vec![AI_RADAR_ASTEROIDS * 4 + 2, 16, 4];
+    let mut population = Population::new(population_size, config, activation, AI_MUT_RATE);
+    let mut best: Option<(NN, f32)> = None;
+    for _generation in 0..generations {
+        let mut fitnesses = Vec::with_capacity(population.players.len());
+        for player in &population.players {
+            let (_won, score) = play_level(&1, Some(player), true).await;
+            if best.as_ref().map_or(true, |(_, best_score)| score > *best_score) {
+                best = Some((player.clone(), score));
+            }
+            fitnesses.push(score);
+        }
+        population.evolve(&fitnesses);
+    }
+    best.expect("population_size must be > 0").0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cast_ray_hits_known_intersection() {
+        // Vertical edge from (10, -5) to (10, 5); a ray from the origin along +x
+        // should hit it exactly at distance 10.
+        let edges = vec![Line::new(Vec2::new(10.0, -5.0), Vec2::new(10.0, 5.0))];
+        let hit = cast_ray(Vec2::new(0.0, 0.0), 0.0, &edges);
+        assert!(matches!(hit, Some(t) if (t - 10.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn cast_ray_misses_when_aimed_past_the_segment() {
+        // Same edge, but the ray points straight down and never crosses it.
+        let edges = vec![Line::new(Vec2::new(10.0, -5.0), Vec2::new(10.0, 5.0))];
+        let hit = cast_ray(Vec2::new(0.0, 0.0), std::f32::consts::FRAC_PI_2, &edges);
+        assert_eq!(hit, None);
+    }
+
+    #[test]
+    fn split_steps_down_tiers_until_gone() {
+        let large = Asteroid {
+            pos: Vec2::new(0.0, 0.0),
+            sides: 5,
+            radius: ASTEROID_TIERS[0],
+            rotation: 0.0,
+            vel: Vec2::new(1.0, 0.0),
+            omega: 0.0,
+            tier: 0,
+        };
+        let medium = large.split();
+        assert_eq!(medium.len(), 2);
+        assert!(medium.iter().all(|a| a.radius == ASTEROID_TIERS[1] && a.tier == 1));
+
+        let small = medium[0].split();
+        assert_eq!(small.len(), 2);
+        assert!(small.iter().all(|a| a.radius == ASTEROID_TIERS[2] && a.tier == 2));
+
+        assert!(small[0].split().is_empty());
+    }
+}
+
 #[macroquad::main("Wavey")]
 async fn main() {
+    let mut autopilot: Option<NN> = None;
     loop {
-        let (_win, play_again) = play_game().await;
+        // press T to breed an autopilot and hand it the controls. The activation is
+        // picked at random so ReLU/Sigmoid/Tanh all get exercised across runs.
+        if is_key_pressed(KeyCode::T) {
+            let activation = match thread_rng().gen_range(0..3) {
+                0 => Activation::ReLU,
+                1 => Activation::Sigmoid,
+                _ => Activation::Tanh,
+            };
+            autopilot = Some(train_autopilot(10, 20, activation).await);
+        }
+        let (_win, play_again) = play_game(autopilot.as_ref()).await;
         if !play_again {
             break;
         }